@@ -1,161 +1,456 @@
-use std::collections::{HashMap, VecDeque};
-use std::ffi::OsStr;
-use std::fs;
-use std::iter::Peekable;
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File, Metadata};
+use std::io::Read;
+use std::io::ErrorKind;
 use std::path::{Path, PathBuf};
-use std::str::Chars;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
 
-pub struct FileInfo {
-    pub path: PathBuf,
-    pub size: u64,
-}
-
-#[derive(Debug, Clone)]
-pub struct DedupOptions {
-    pub filters: Vec<String>,
-    pub exclude_empty: bool,
-    pub case_sensitive: bool,
-}
+use anyhow::Result;
+use rayon::prelude::*;
 
-pub struct DuplicateGroup {
-    pub files: Vec<FileInfo>,
-}
+use crate::filter::matches_filters;
+use crate::ignore::IgnoreSet;
+use crate::types::{DedupOptions, DuplicateFiles, DuplicateGroup, FileInfo};
 
-pub struct DuplicateFiles {
-    pub groups: Vec<DuplicateGroup>,
-}
+// Only the first prefix of a file is read for the partial hash; this is cheap
+// and already separates most same-size files that differ near their start.
+const PARTIAL_HASH_SIZE: usize = 4096;
+// Full-file hashing streams the file in blocks of this size so large files
+// never have to be held in memory at once.
+const FULL_HASH_BLOCK_SIZE: usize = 64 * 1024;
 
-pub fn find_duplicates(
-    folder_path: &Path,
-    options: &DedupOptions,
-) -> Result<DuplicateFiles, String> {
+pub fn find_duplicates(folder_path: &Path, options: &DedupOptions) -> Result<DuplicateFiles> {
     let mut size_map = scan_directory(folder_path, options)?;
 
+    let scanned_files = size_map.values().map(Vec::len).sum();
+
     // Remove entries with only one file (no duplicates)
     size_map.retain(|_, files| files.len() > 1);
 
+    // When we only care about size there is nothing more to do: every
+    // surviving size group is a duplicate group.
+    if options.only_compare_file_size {
+        let groups = size_map
+            .into_values()
+            .map(|files| DuplicateGroup { files })
+            .collect();
+        return Ok(DuplicateFiles {
+            groups,
+            scanned_files,
+            size_only: true,
+        });
+    }
+
+    // Otherwise narrow each size group down by partial and then full content
+    // hashes, reading as little as possible at every step. Size groups are
+    // independent, so they are hashed in parallel.
     let groups = size_map
-        .into_iter()
-        .map(|(_, files)| DuplicateGroup { files })
+        .into_par_iter()
+        .flat_map(|(_, files)| narrow_size_group(files))
         .collect();
 
-    Ok(DuplicateFiles { groups })
+    Ok(DuplicateFiles {
+        groups,
+        scanned_files,
+        size_only: false,
+    })
 }
 
-fn scan_directory(
-    dir: &Path,
-    options: &DedupOptions,
-) -> Result<HashMap<u64, Vec<FileInfo>>, String> {
-    let mut size_map: HashMap<u64, Vec<FileInfo>> = HashMap::new();
+// Resolve a single same-size group into the duplicate groups it actually
+// contains by sub-grouping on the partial and then the full hash.
+fn narrow_size_group(files: Vec<FileInfo>) -> Vec<DuplicateGroup> {
+    let mut groups = Vec::new();
+    for candidates in group_by_partial_hash(files) {
+        if candidates.len() < 2 {
+            continue;
+        }
+        for group in group_by_full_hash(candidates) {
+            if group.len() > 1 {
+                groups.push(DuplicateGroup { files: group });
+            }
+        }
+    }
+    groups
+}
 
-    let mut queue: VecDeque<PathBuf> = VecDeque::new();
-    queue.push_back(dir.to_path_buf());
+// Which files to remove from every duplicate group. `None` reports only.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeleteMethod {
+    None,
+    AllExceptNewest,
+    AllExceptOldest,
+    OneNewest,
+    OneOldest,
+}
 
-    while let Some(current_dir) = queue.pop_front() {
-        let entries = match fs::read_dir(&current_dir) {
-            Ok(entries) => entries,
-            Err(e) => return Err(format!("Failed to read directory: {}", e)),
-        };
+impl DeleteMethod {
+    pub fn parse(value: &str) -> Option<DeleteMethod> {
+        match value {
+            "none" => Some(DeleteMethod::None),
+            "all-except-newest" => Some(DeleteMethod::AllExceptNewest),
+            "all-except-oldest" => Some(DeleteMethod::AllExceptOldest),
+            "one-newest" => Some(DeleteMethod::OneNewest),
+            "one-oldest" => Some(DeleteMethod::OneOldest),
+            _ => None,
+        }
+    }
+}
 
-        for entry in entries {
-            let entry = match entry {
-                Ok(entry) => entry,
-                Err(e) => return Err(format!("Failed to read directory entry: {}", e)),
-            };
+// What happened to a single duplicate group after applying a `DeleteMethod`.
+pub struct GroupDeletion {
+    pub reclaimed: u64,
+    pub removed: Vec<PathBuf>,
+}
 
-            let path = entry.path();
+// A file we tried but failed to remove.
+pub struct DeletionFailure {
+    pub path: PathBuf,
+    pub error: String,
+}
 
-            if path.is_dir() {
-                queue.push_back(path);
-                continue;
-            }
+// Summary of a deletion run: one entry per group that lost files, the total
+// "lost space" reclaimed, and every per-file failure collected along the way.
+pub struct DeletionReport {
+    pub groups: Vec<GroupDeletion>,
+    pub total_reclaimed: u64,
+    pub failures: Vec<DeletionFailure>,
+}
 
-            if !path.is_file() || !matches_filters(&path, &options.filters, options.case_sensitive)
-            {
-                continue;
-            }
+// Remove files from each duplicate group according to `method`, keyed off the
+// files' modification (falling back to creation) timestamps. Failures are
+// collected rather than aborting the run so one undeletable file doesn't stop
+// the rest.
+//
+// Refuses outright when the groups were formed by size alone: such groups are
+// not verified duplicates, so deleting from them would destroy differing data.
+pub fn delete_duplicates(
+    duplicates: &DuplicateFiles,
+    method: DeleteMethod,
+) -> Result<DeletionReport> {
+    if duplicates.size_only {
+        anyhow::bail!(
+            "refusing to delete: groups were matched by size only and are not \
+             verified duplicates; drop --size-only to compare file contents"
+        );
+    }
 
-            let metadata = match path.metadata() {
-                Ok(metadata) => metadata,
-                Err(_) => continue, // Skip files we can't get metadata for
-            };
+    let mut report = DeletionReport {
+        groups: Vec::new(),
+        total_reclaimed: 0,
+        failures: Vec::new(),
+    };
 
-            let size = metadata.len();
+    if method == DeleteMethod::None {
+        return Ok(report);
+    }
 
-            if size == 0 && options.exclude_empty {
-                continue;
+    for group in &duplicates.groups {
+        let mut removed = Vec::new();
+        let mut reclaimed = 0;
+
+        for file in files_to_remove(&group.files, method) {
+            match fs::remove_file(&file.path) {
+                Ok(()) => {
+                    reclaimed += file.metadata.len();
+                    removed.push(file.path.clone());
+                }
+                Err(e) => report.failures.push(DeletionFailure {
+                    path: file.path.clone(),
+                    error: e.to_string(),
+                }),
             }
+        }
 
-            let file_info = FileInfo {
-                path: path.clone(),
-                size,
-            };
-            size_map
-                .entry(size)
-                .or_insert_with(Vec::new)
-                .push(file_info);
+        if !removed.is_empty() {
+            report.total_reclaimed += reclaimed;
+            report.groups.push(GroupDeletion { reclaimed, removed });
         }
     }
 
-    Ok(size_map)
+    Ok(report)
+}
+
+// Pick the files to remove from a single group. `AllExcept*` keeps one file and
+// drops the rest; `One*` drops a single file.
+fn files_to_remove(files: &[FileInfo], method: DeleteMethod) -> Vec<&FileInfo> {
+    if files.len() < 2 {
+        return Vec::new();
+    }
+
+    let newest = files
+        .iter()
+        .max_by_key(|f| modified_time(f))
+        .expect("group is non-empty");
+    let oldest = files
+        .iter()
+        .min_by_key(|f| modified_time(f))
+        .expect("group is non-empty");
+
+    match method {
+        DeleteMethod::None => Vec::new(),
+        DeleteMethod::AllExceptNewest => files
+            .iter()
+            .filter(|f| !std::ptr::eq(*f, newest))
+            .collect(),
+        DeleteMethod::AllExceptOldest => files
+            .iter()
+            .filter(|f| !std::ptr::eq(*f, oldest))
+            .collect(),
+        DeleteMethod::OneNewest => vec![newest],
+        DeleteMethod::OneOldest => vec![oldest],
+    }
+}
+
+fn modified_time(file: &FileInfo) -> SystemTime {
+    file.metadata
+        .modified()
+        .or_else(|_| file.metadata.created())
+        .unwrap_or(UNIX_EPOCH)
 }
 
-// Verifies that the file matches the filter which may contain wildcards.
-// E.g. "*.txt" will match "file.txt" and "file2.txt" but not "file.docx".
-// *.a?b will match a.acb or a.aab but not a.a_something_b
-fn matches_filter(path: &Path, filter: &str, case_sensitive: bool) -> bool {
-    if filter.is_empty() || filter == "*" {
-        return true;
+// Walk `dir` in parallel, grouping every file that passes the filters by size.
+// Each discovered subdirectory is turned into its own rayon task that feeds a
+// shared, lock-protected size map. A directory that may not be read is skipped,
+// as is any entry whose type or metadata cannot be read; the first other
+// failure to read a directory is surfaced to the caller.
+fn scan_directory(dir: &Path, options: &DedupOptions) -> Result<HashMap<u64, Vec<FileInfo>>> {
+    let size_map: Mutex<HashMap<u64, Vec<FileInfo>>> = Mutex::new(HashMap::new());
+    let first_error: Mutex<Option<anyhow::Error>> = Mutex::new(None);
+    // Tracks the `(dev, ino)` pairs already seen so that several hard links to
+    // one inode collapse into a single logical file.
+    let seen_inodes: Mutex<HashSet<(u64, u64)>> = Mutex::new(HashSet::new());
+
+    rayon::scope(|scope| {
+        walk(
+            dir.to_path_buf(),
+            options,
+            options.ignore.clone(),
+            &size_map,
+            &first_error,
+            &seen_inodes,
+            scope,
+        );
+    });
+
+    if let Some(err) = first_error.into_inner().unwrap() {
+        return Err(err);
     }
 
-    let chars_eq = |a: &char, b: &char| -> bool {
-        if case_sensitive {
-            a == b
-        } else {
-            a.eq_ignore_ascii_case(&b)
+    Ok(size_map.into_inner().unwrap())
+}
+
+fn walk<'a>(
+    dir: PathBuf,
+    options: &'a DedupOptions<'a>,
+    mut ignore: IgnoreSet,
+    size_map: &'a Mutex<HashMap<u64, Vec<FileInfo>>>,
+    first_error: &'a Mutex<Option<anyhow::Error>>,
+    seen_inodes: &'a Mutex<HashSet<(u64, u64)>>,
+    scope: &rayon::Scope<'a>,
+) {
+    // Ignore rules compound as we descend, so each directory inherits a copy of
+    // its parent's set extended with any ignore files it holds.
+    ignore.add_dir_defaults(&dir);
+
+    let entries = match fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        // A directory we may not read is skipped, not fatal.
+        Err(e) if e.kind() == ErrorKind::PermissionDenied => return,
+        Err(e) => {
+            record_error(first_error, anyhow::Error::new(e).context(format!(
+                "Failed to read directory {}",
+                dir.display()
+            )));
+            return;
         }
     };
 
-    let file_name = path.file_name().unwrap_or(OsStr::new("")).to_string_lossy();
-
-    let mut filter_iter = filter.chars().peekable();
-    let mut file_name_iter = file_name.chars().peekable();
+    for entry in entries {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(_) => continue, // Skip entries we can't stat.
+        };
+        let path = entry.path();
+        let file_type = match entry.file_type() {
+            Ok(file_type) => file_type,
+            Err(_) => continue,
+        };
 
-    let mut star_filter_iter: Option<Peekable<Chars>> = None;
-    let mut star_file_name_iter: Peekable<Chars> = file_name_iter.clone();
+        if file_type.is_dir() {
+            if !ignore.is_empty() && ignore.is_ignored(&path, true) {
+                continue;
+            }
+            let child_ignore = ignore.clone();
+            scope.spawn(move |scope| {
+                walk(
+                    path,
+                    options,
+                    child_ignore,
+                    size_map,
+                    first_error,
+                    seen_inodes,
+                    scope,
+                );
+            });
+            continue;
+        }
 
-    while let Some(file_name_char) = file_name_iter.peek() {
-        let filter_char = filter_iter.peek();
-        if filter_char
-            .is_some_and(|filter_char| filter_char == &'?' || chars_eq(filter_char, file_name_char))
+        if !file_type.is_file()
+            || !matches_filters(&path, options.filters, options.case_sensitive)
         {
-            filter_iter.next();
-            file_name_iter.next();
-        } else if filter_char.is_some_and(|filter_char| filter_char == &'*') {
-            star_filter_iter = Some(filter_iter.clone());
-            star_file_name_iter = file_name_iter.clone();
-            filter_iter.next();
-        } else if let Some(star_filter_iter) = star_filter_iter.clone() {
-            filter_iter = star_filter_iter;
-            star_file_name_iter.next();
-            file_name_iter = star_file_name_iter.clone();
-        } else {
-            return false;
+            continue;
+        }
+
+        if !ignore.is_empty() && ignore.is_ignored(&path, false) {
+            continue;
+        }
+
+        // Metadata is only collected once a file has passed the name and ignore
+        // filters, so files rejected by those are never stat'ed.
+        let metadata = match path.metadata() {
+            Ok(metadata) => metadata,
+            Err(_) => continue,
+        };
+
+        if options.exclude_empty && metadata.len() == 0 {
+            continue;
+        }
+
+        if metadata.len() < options.min_size {
+            continue;
+        }
+
+        // Skip further hard links to an inode we've already recorded; they are
+        // the same physical bytes and reclaim no space.
+        if is_known_inode(seen_inodes, &metadata) {
+            continue;
+        }
+
+        size_map
+            .lock()
+            .unwrap()
+            .entry(metadata.len())
+            .or_default()
+            .push(FileInfo::new(path, metadata));
+    }
+}
+
+fn record_error(first_error: &Mutex<Option<anyhow::Error>>, err: anyhow::Error) {
+    let mut slot = first_error.lock().unwrap();
+    if slot.is_none() {
+        *slot = Some(err);
+    }
+}
+
+// Returns true when this file shares its `(dev, ino)` with one already seen.
+// Only Unix exposes inode identity; elsewhere every file is treated as distinct.
+#[cfg(unix)]
+fn is_known_inode(seen_inodes: &Mutex<HashSet<(u64, u64)>>, metadata: &Metadata) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    !seen_inodes
+        .lock()
+        .unwrap()
+        .insert((metadata.dev(), metadata.ino()))
+}
+
+#[cfg(not(unix))]
+fn is_known_inode(_seen_inodes: &Mutex<HashSet<(u64, u64)>>, _metadata: &Metadata) -> bool {
+    false
+}
+
+// Sub-group files by a hash of their first `PARTIAL_HASH_SIZE` bytes. Files we
+// cannot open or read are dropped silently so a single unreadable file never
+// aborts the whole scan.
+fn group_by_partial_hash(files: Vec<FileInfo>) -> Vec<Vec<FileInfo>> {
+    let mut map: HashMap<u128, Vec<FileInfo>> = HashMap::new();
+    for mut file in files {
+        let hash = match partial_hash(&file.path) {
+            Some(hash) => hash,
+            None => continue,
+        };
+        file.partial_hash = Some(hash);
+        map.entry(hash).or_default().push(file);
+    }
+    map.into_values().collect()
+}
+
+// Sub-group files by a hash of their entire contents. The full hash is stored
+// on the `FileInfo` so it is computed exactly once.
+fn group_by_full_hash(files: Vec<FileInfo>) -> Vec<Vec<FileInfo>> {
+    let mut map: HashMap<u128, Vec<FileInfo>> = HashMap::new();
+    for mut file in files {
+        let hash = match full_hash(&file.path) {
+            Some(hash) => hash,
+            None => continue,
+        };
+        file.full_hash = Some(hash);
+        map.entry(hash).or_default().push(file);
+    }
+    map.into_values().collect()
+}
+
+fn partial_hash(path: &Path) -> Option<u128> {
+    let mut file = File::open(path).ok()?;
+    let mut buffer = [0u8; PARTIAL_HASH_SIZE];
+
+    let mut read = 0;
+    while read < buffer.len() {
+        match file.read(&mut buffer[read..]) {
+            Ok(0) => break,
+            Ok(n) => read += n,
+            Err(_) => return None,
         }
     }
 
-    let remaining_all_stars = filter_iter.all(|f| return f == '*');
-    remaining_all_stars
+    let mut hasher = Fnv1a::new();
+    hasher.update(&buffer[..read]);
+    Some(hasher.finish())
 }
 
-fn matches_filters(path: &Path, filters: &[String], case_sensitive: bool) -> bool {
-    if filters.is_empty() || filters.contains(&"*".to_string()) {
-        return true;
+fn full_hash(path: &Path) -> Option<u128> {
+    let mut file = File::open(path).ok()?;
+    let mut buffer = vec![0u8; FULL_HASH_BLOCK_SIZE];
+    let mut hasher = Fnv1a::new();
+
+    loop {
+        match file.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(n) => hasher.update(&buffer[..n]),
+            Err(_) => return None,
+        }
     }
 
-    return filters
-        .iter()
-        .any(|filter| matches_filter(path, filter, case_sensitive));
+    Some(hasher.finish())
+}
+
+// 128-bit FNV-1a. A non-cryptographic hash is fine here: we only need a low
+// collision probability to decide which files are worth a byte-for-byte... well,
+// a full-hash comparison, and keeping it dependency-free keeps the tool small.
+struct Fnv1a {
+    state: u128,
+}
+
+impl Fnv1a {
+    const OFFSET_BASIS: u128 = 0x6c62_272e_07bb_0142_62b8_2175_6295_c58d;
+    const PRIME: u128 = 0x0000_0000_0100_0000_0000_0000_0000_013b;
+
+    fn new() -> Self {
+        Fnv1a {
+            state: Self::OFFSET_BASIS,
+        }
+    }
+
+    fn update(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.state ^= byte as u128;
+            self.state = self.state.wrapping_mul(Self::PRIME);
+        }
+    }
+
+    fn finish(&self) -> u128 {
+        self.state
+    }
 }
 
 #[cfg(test)]
@@ -163,50 +458,28 @@ mod tests {
     use super::*;
     use std::fs::File;
     use std::io::Write;
-    use std::path::Path;
     use tempfile::tempdir;
 
-    #[test]
-    fn test_matches_filter() {
-        assert!(matches_filter(Path::new("a.txt"), "*", true));
-        assert!(matches_filter(Path::new("a.txt"), "?.???", true));
-        assert!(!matches_filter(Path::new("a.txt"), "?.??", false));
-        assert!(matches_filter(Path::new("a.txt"), "*.*?", true));
-        assert!(!matches_filter(Path::new("a"), "aa", false));
-        assert!(!matches_filter(Path::new("A"), "a", true));
-        assert!(matches_filter(Path::new("A"), "***********", true));
-    }
-
-    #[test]
-    fn test_matches_filters() {
-        assert!(matches_filters(Path::new("c:\\temp\\test.txt"), &[], true));
-        assert!(matches_filters(
-            Path::new("c:\\temp\\test.txt"),
-            &["*test*".to_string()],
-            true
-        ));
-        assert!(!matches_filters(
-            Path::new("c:\\temp\\test.txt"),
-            &["nonexistent".to_string()],
-            true
-        ));
-        assert!(matches_filters(
-            Path::new("/home/user/test.txt"),
-            &["test*".to_string()],
-            true
-        ));
-        assert!(matches_filters(
-            Path::new("/home/user/test.txt"),
-            &["*.txt".to_string()],
-            true
-        ));
+    fn options(only_compare_file_size: bool) -> DedupOptions<'static> {
+        use crate::ignore::IgnoreSet;
+        // Leaked so the returned options can borrow it with a 'static lifetime;
+        // this only ever runs inside short-lived unit tests.
+        let ignore: &'static IgnoreSet = Box::leak(Box::new(IgnoreSet::default()));
+        DedupOptions {
+            filters: &[],
+            exclude_empty: false,
+            case_sensitive: true,
+            only_compare_file_size,
+            ignore,
+            min_size: 0,
+        }
     }
 
     #[test]
     fn test_find_duplicates() {
         let temp_dir = tempdir().unwrap();
 
-        // Create two files with the same size
+        // Create two files with the same content
         let content = b"Hello, World!";
         let file1_path = temp_dir.path().join("file1.txt");
         let file2_path = temp_dir.path().join("file2.txt");
@@ -217,12 +490,7 @@ mod tests {
         file1.write_all(content).unwrap();
         file2.write_all(content).unwrap();
 
-        let options = DedupOptions {
-            filters: Vec::new(),
-            exclude_empty: false,
-            case_sensitive: true,
-        };
-        let duplicates = find_duplicates(temp_dir.path(), &options).unwrap();
+        let duplicates = find_duplicates(temp_dir.path(), &options(false)).unwrap();
 
         assert_eq!(duplicates.groups.len(), 1);
         assert_eq!(duplicates.groups[0].files.len(), 2);
@@ -237,6 +505,201 @@ mod tests {
         assert!(paths.contains(&file2_path.to_string_lossy().to_string()));
     }
 
+    #[test]
+    fn test_same_size_different_content() {
+        let temp_dir = tempdir().unwrap();
+
+        // Two files of equal length but different bytes.
+        let file1_path = temp_dir.path().join("a.bin");
+        let file2_path = temp_dir.path().join("b.bin");
+        File::create(&file1_path)
+            .unwrap()
+            .write_all(b"aaaaaaaaaa")
+            .unwrap();
+        File::create(&file2_path)
+            .unwrap()
+            .write_all(b"bbbbbbbbbb")
+            .unwrap();
+
+        // Content comparison rejects them...
+        assert_eq!(
+            find_duplicates(temp_dir.path(), &options(false))
+                .unwrap()
+                .groups
+                .len(),
+            0
+        );
+        // ...but size-only comparison still groups them together.
+        assert_eq!(
+            find_duplicates(temp_dir.path(), &options(true))
+                .unwrap()
+                .groups
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_scan_respects_ignore_files() {
+        use std::path::PathBuf;
+
+        let temp_dir = tempdir().unwrap();
+
+        // A duplicate pair where one member lives under an ignored directory.
+        let content = b"shared bytes";
+        let keep = temp_dir.path().join("keep.bin");
+        let sub = temp_dir.path().join("build");
+        std::fs::create_dir(&sub).unwrap();
+        let ignored = sub.join("copy.bin");
+        File::create(&keep).unwrap().write_all(content).unwrap();
+        File::create(&ignored).unwrap().write_all(content).unwrap();
+
+        // Write a .gitignore that prunes the `build/` directory.
+        File::create(temp_dir.path().join(".gitignore"))
+            .unwrap()
+            .write_all(b"build/\n")
+            .unwrap();
+
+        let ignore: &'static IgnoreSet = Box::leak(Box::new(IgnoreSet::default()));
+        let opts = DedupOptions {
+            filters: &[],
+            exclude_empty: false,
+            case_sensitive: true,
+            only_compare_file_size: false,
+            ignore,
+            min_size: 0,
+        };
+
+        // With the ignored copy pruned there is no duplicate left.
+        let duplicates = find_duplicates(temp_dir.path(), &opts).unwrap();
+        assert!(duplicates.groups.is_empty());
+
+        // Sanity check that the ignored path really existed.
+        assert!(PathBuf::from(&ignored).exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_hardlinks_collapse_to_one_logical_file() {
+        let temp_dir = tempdir().unwrap();
+
+        let content = b"linked content";
+        let original = temp_dir.path().join("original.bin");
+        let link = temp_dir.path().join("link.bin");
+        let separate = temp_dir.path().join("separate.bin");
+
+        File::create(&original).unwrap().write_all(content).unwrap();
+        std::fs::hard_link(&original, &link).unwrap();
+        File::create(&separate).unwrap().write_all(content).unwrap();
+
+        let duplicates = find_duplicates(temp_dir.path(), &options(false)).unwrap();
+
+        // The two hard links count once, so only two logical files are scanned
+        // and the duplicate group holds the inode representative plus the
+        // separate copy.
+        assert_eq!(duplicates.scanned_files, 2);
+        assert_eq!(duplicates.groups.len(), 1);
+        assert_eq!(duplicates.groups[0].files.len(), 2);
+    }
+
+    #[test]
+    fn test_min_size_skips_small_files() {
+        let temp_dir = tempdir().unwrap();
+
+        // A pair of tiny duplicates and a pair of larger duplicates.
+        for name in ["tiny1.bin", "tiny2.bin"] {
+            File::create(temp_dir.path().join(name))
+                .unwrap()
+                .write_all(b"xx")
+                .unwrap();
+        }
+        let big = vec![b'z'; 4096];
+        for name in ["big1.bin", "big2.bin"] {
+            File::create(temp_dir.path().join(name))
+                .unwrap()
+                .write_all(&big)
+                .unwrap();
+        }
+
+        let mut opts = options(false);
+        opts.min_size = 1024;
+        let duplicates = find_duplicates(temp_dir.path(), &opts).unwrap();
+
+        // Only the large pair survives the threshold.
+        assert_eq!(duplicates.scanned_files, 2);
+        assert_eq!(duplicates.groups.len(), 1);
+        assert_eq!(duplicates.groups[0].files.len(), 2);
+    }
+
+    #[test]
+    fn test_delete_all_except_one() {
+        let temp_dir = tempdir().unwrap();
+
+        let content = b"duplicate payload";
+        let file1_path = temp_dir.path().join("one.txt");
+        let file2_path = temp_dir.path().join("two.txt");
+        File::create(&file1_path).unwrap().write_all(content).unwrap();
+        File::create(&file2_path).unwrap().write_all(content).unwrap();
+
+        let duplicates = find_duplicates(temp_dir.path(), &options(false)).unwrap();
+        assert_eq!(duplicates.groups.len(), 1);
+
+        let report = delete_duplicates(&duplicates, DeleteMethod::AllExceptNewest).unwrap();
+
+        // One of the two files is gone, the other stays, and the reclaimed
+        // space matches the removed file's size.
+        assert_eq!(report.groups.len(), 1);
+        assert_eq!(report.groups[0].removed.len(), 1);
+        assert_eq!(report.total_reclaimed, content.len() as u64);
+        assert!(report.failures.is_empty());
+
+        let remaining = [&file1_path, &file2_path]
+            .iter()
+            .filter(|p| p.exists())
+            .count();
+        assert_eq!(remaining, 1);
+    }
+
+    #[test]
+    fn test_delete_none_removes_nothing() {
+        let temp_dir = tempdir().unwrap();
+
+        let content = b"keep me";
+        let file1_path = temp_dir.path().join("a.txt");
+        let file2_path = temp_dir.path().join("b.txt");
+        File::create(&file1_path).unwrap().write_all(content).unwrap();
+        File::create(&file2_path).unwrap().write_all(content).unwrap();
+
+        let duplicates = find_duplicates(temp_dir.path(), &options(false)).unwrap();
+        let report = delete_duplicates(&duplicates, DeleteMethod::None).unwrap();
+
+        assert_eq!(report.total_reclaimed, 0);
+        assert!(report.groups.is_empty());
+        assert!(file1_path.exists() && file2_path.exists());
+    }
+
+    #[test]
+    fn test_delete_refuses_in_size_only_mode() {
+        let temp_dir = tempdir().unwrap();
+
+        // Two files of equal length but differing content: a size-only scan
+        // wrongly groups them, so deletion must refuse rather than destroy one.
+        let file1_path = temp_dir.path().join("a.bin");
+        let file2_path = temp_dir.path().join("b.bin");
+        File::create(&file1_path).unwrap().write_all(b"0123456789").unwrap();
+        File::create(&file2_path).unwrap().write_all(b"9876543210").unwrap();
+
+        let duplicates = find_duplicates(temp_dir.path(), &options(true)).unwrap();
+        assert!(duplicates.size_only);
+        assert_eq!(duplicates.groups.len(), 1);
+
+        let result = delete_duplicates(&duplicates, DeleteMethod::AllExceptNewest);
+        assert!(result.is_err());
+
+        // Nothing was touched.
+        assert!(file1_path.exists() && file2_path.exists());
+    }
+
     #[test]
     fn test_empty_files_handling() {
         let temp_dir = tempdir().unwrap();
@@ -254,21 +717,13 @@ mod tests {
         file3.write_all(content).unwrap();
 
         // Test with exclude empty
-        let options = DedupOptions {
-            filters: Vec::new(),
-            exclude_empty: true,
-            case_sensitive: true,
-        };
-        let duplicates = find_duplicates(temp_dir.path(), &options).unwrap();
+        let mut opts = options(false);
+        opts.exclude_empty = true;
+        let duplicates = find_duplicates(temp_dir.path(), &opts).unwrap();
         assert_eq!(duplicates.groups.len(), 0); // No duplicates found
 
         // Test with include empty
-        let options = DedupOptions {
-            filters: Vec::new(),
-            exclude_empty: false,
-            case_sensitive: true,
-        };
-        let duplicates = find_duplicates(temp_dir.path(), &options).unwrap();
+        let duplicates = find_duplicates(temp_dir.path(), &options(false)).unwrap();
         assert_eq!(duplicates.groups.len(), 1); // Empty files are considered duplicates
         assert_eq!(duplicates.groups[0].files.len(), 2); // Two empty files
 
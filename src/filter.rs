@@ -0,0 +1,102 @@
+use std::ffi::OsStr;
+use std::iter::Peekable;
+use std::path::Path;
+use std::str::Chars;
+
+// Verifies that the file matches the filter which may contain wildcards.
+// E.g. "*.txt" will match "file.txt" and "file2.txt" but not "file.docx".
+// *.a?b will match a.acb or a.aab but not a.a_something_b
+fn matches_filter(path: &Path, filter: &str, case_sensitive: bool) -> bool {
+    if filter.is_empty() || filter == "*" {
+        return true;
+    }
+
+    let chars_eq = |a: &char, b: &char| -> bool {
+        if case_sensitive {
+            a == b
+        } else {
+            a.eq_ignore_ascii_case(b)
+        }
+    };
+
+    let file_name = path.file_name().unwrap_or(OsStr::new("")).to_string_lossy();
+
+    let mut filter_iter = filter.chars().peekable();
+    let mut file_name_iter = file_name.chars().peekable();
+
+    let mut star_filter_iter: Option<Peekable<Chars>> = None;
+    let mut star_file_name_iter: Peekable<Chars> = file_name_iter.clone();
+
+    while let Some(file_name_char) = file_name_iter.peek() {
+        let filter_char = filter_iter.peek();
+        if filter_char
+            .is_some_and(|filter_char| filter_char == &'?' || chars_eq(filter_char, file_name_char))
+        {
+            filter_iter.next();
+            file_name_iter.next();
+        } else if filter_char.is_some_and(|filter_char| filter_char == &'*') {
+            star_filter_iter = Some(filter_iter.clone());
+            star_file_name_iter = file_name_iter.clone();
+            filter_iter.next();
+        } else if let Some(star_filter_iter) = star_filter_iter.clone() {
+            filter_iter = star_filter_iter;
+            star_file_name_iter.next();
+            file_name_iter = star_file_name_iter.clone();
+        } else {
+            return false;
+        }
+    }
+
+    filter_iter.all(|f| f == '*')
+}
+
+pub fn matches_filters(path: &Path, filters: &[&str], case_sensitive: bool) -> bool {
+    if filters.is_empty() || filters.contains(&"*") {
+        return true;
+    }
+
+    filters
+        .iter()
+        .any(|filter| matches_filter(path, filter, case_sensitive))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches_filter() {
+        assert!(matches_filter(Path::new("a.txt"), "*", true));
+        assert!(matches_filter(Path::new("a.txt"), "?.???", true));
+        assert!(!matches_filter(Path::new("a.txt"), "?.??", false));
+        assert!(matches_filter(Path::new("a.txt"), "*.*?", true));
+        assert!(!matches_filter(Path::new("a"), "aa", false));
+        assert!(!matches_filter(Path::new("A"), "a", true));
+        assert!(matches_filter(Path::new("A"), "***********", true));
+    }
+
+    #[test]
+    fn test_matches_filters() {
+        assert!(matches_filters(Path::new("c:\\temp\\test.txt"), &[], true));
+        assert!(matches_filters(
+            Path::new("c:\\temp\\test.txt"),
+            &["*test*"],
+            true
+        ));
+        assert!(!matches_filters(
+            Path::new("c:\\temp\\test.txt"),
+            &["nonexistent"],
+            true
+        ));
+        assert!(matches_filters(
+            Path::new("/home/user/test.txt"),
+            &["test*"],
+            true
+        ));
+        assert!(matches_filters(
+            Path::new("/home/user/test.txt"),
+            &["*.txt"],
+            true
+        ));
+    }
+}
@@ -0,0 +1,220 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+// A compiled set of gitignore-style patterns. Patterns are matched in the order
+// they were added and the last one that matches a path decides its fate, so a
+// later `!`-negated pattern can re-include something an earlier pattern excluded.
+#[derive(Debug, Clone, Default)]
+pub struct IgnoreSet {
+    patterns: Vec<IgnorePattern>,
+}
+
+#[derive(Debug, Clone)]
+struct IgnorePattern {
+    // Directory the pattern is relative to (the directory of the ignore file it
+    // came from).
+    base: PathBuf,
+    // The wildcard body, with the `!`/trailing-`/`/leading-`/` markers removed.
+    glob: String,
+    negated: bool,
+    dir_only: bool,
+    // `true` when the pattern is tied to `base` (it contained a `/`); otherwise
+    // it matches a bare file name at any depth below `base`.
+    anchored: bool,
+}
+
+impl IgnoreSet {
+    // Append the patterns from `path`, anchoring them to that file's directory.
+    pub fn add_file(&mut self, path: &Path) -> Result<()> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read ignore file {}", path.display()))?;
+        let base = path.parent().unwrap_or_else(|| Path::new("")).to_path_buf();
+        for line in contents.lines() {
+            if let Some(pattern) = IgnorePattern::parse(line, &base) {
+                self.patterns.push(pattern);
+            }
+        }
+        Ok(())
+    }
+
+    // Load the well-known ignore files (`.gitignore`, `.ignore`) sitting directly
+    // in `dir`, if present. Errors reading an individual file are ignored so a
+    // stray unreadable ignore file doesn't abort the walk.
+    pub fn add_dir_defaults(&mut self, dir: &Path) {
+        for name in [".gitignore", ".ignore"] {
+            let candidate = dir.join(name);
+            if candidate.is_file() {
+                let _ = self.add_file(&candidate);
+            }
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.patterns.is_empty()
+    }
+
+    // Decide whether `path` should be excluded. `is_dir` lets directory-only
+    // patterns (those ending in `/`) apply to directories alone.
+    pub fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        let mut ignored = false;
+        for pattern in &self.patterns {
+            if pattern.matches(path, is_dir) {
+                ignored = !pattern.negated;
+            }
+        }
+        ignored
+    }
+}
+
+impl IgnorePattern {
+    fn parse(line: &str, base: &Path) -> Option<IgnorePattern> {
+        let line = line.trim_end();
+        if line.is_empty() || line.starts_with('#') {
+            return None;
+        }
+
+        let mut rest = line;
+        let negated = rest.starts_with('!');
+        if negated {
+            rest = &rest[1..];
+        }
+
+        let dir_only = rest.ends_with('/');
+        if dir_only {
+            rest = &rest[..rest.len() - 1];
+        }
+
+        // A leading slash or any interior slash anchors the pattern to `base`.
+        let anchored = rest.contains('/');
+        let glob = rest.strip_prefix('/').unwrap_or(rest).to_string();
+        if glob.is_empty() {
+            return None;
+        }
+
+        Some(IgnorePattern {
+            base: base.to_path_buf(),
+            glob,
+            negated,
+            dir_only,
+            anchored,
+        })
+    }
+
+    fn matches(&self, path: &Path, is_dir: bool) -> bool {
+        if self.dir_only && !is_dir {
+            return false;
+        }
+
+        let relative = match path.strip_prefix(&self.base) {
+            Ok(relative) => relative,
+            Err(_) => return false,
+        };
+        let relative = relative.to_string_lossy().replace('\\', "/");
+
+        if self.anchored {
+            path_match(&self.glob, &relative)
+        } else {
+            // Unanchored patterns match a file name at any depth.
+            match path.file_name() {
+                Some(name) => wildcard_match(&self.glob, &name.to_string_lossy()),
+                None => false,
+            }
+        }
+    }
+}
+
+// Match a slash-separated glob against a slash-separated path. `**` spans any
+// number of path segments; `*` and `?` stay within a single segment.
+fn path_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<&str> = pattern.split('/').collect();
+    let text: Vec<&str> = text.split('/').collect();
+    match_segments(&pattern, &text)
+}
+
+fn match_segments(pattern: &[&str], text: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => text.is_empty(),
+        Some((&"**", rest)) => (0..=text.len()).any(|i| match_segments(rest, &text[i..])),
+        Some((&first, rest)) => match text.split_first() {
+            Some((&head, tail)) if wildcard_match(first, head) => match_segments(rest, tail),
+            _ => false,
+        },
+    }
+}
+
+// Classic `*`/`?` wildcard match over a single string. `*` matches any run of
+// characters (including none); `?` matches exactly one.
+fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+
+    let mut pi = 0;
+    let mut ti = 0;
+    let mut star: Option<(usize, usize)> = None;
+
+    while ti < text.len() {
+        if pi < pattern.len() && (pattern[pi] == '?' || pattern[pi] == text[ti]) {
+            pi += 1;
+            ti += 1;
+        } else if pi < pattern.len() && pattern[pi] == '*' {
+            star = Some((pi, ti));
+            pi += 1;
+        } else if let Some((star_pi, star_ti)) = star {
+            pi = star_pi + 1;
+            ti = star_ti + 1;
+            star = Some((star_pi, ti));
+        } else {
+            return false;
+        }
+    }
+
+    while pi < pattern.len() && pattern[pi] == '*' {
+        pi += 1;
+    }
+    pi == pattern.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(base: &str, lines: &[&str]) -> IgnoreSet {
+        let base = PathBuf::from(base);
+        let patterns = lines
+            .iter()
+            .filter_map(|line| IgnorePattern::parse(line, &base))
+            .collect();
+        IgnoreSet { patterns }
+    }
+
+    #[test]
+    fn test_unanchored_name_matches_any_depth() {
+        let set = set("/root", &["target/", "node_modules/"]);
+        assert!(set.is_ignored(Path::new("/root/target"), true));
+        assert!(set.is_ignored(Path::new("/root/a/b/target"), true));
+        // Directory-only patterns don't match files of the same name.
+        assert!(!set.is_ignored(Path::new("/root/target"), false));
+    }
+
+    #[test]
+    fn test_anchored_pattern_matches_only_at_root() {
+        let set = set("/root", &["/build"]);
+        assert!(set.is_ignored(Path::new("/root/build"), true));
+        assert!(!set.is_ignored(Path::new("/root/sub/build"), true));
+    }
+
+    #[test]
+    fn test_negation_reincludes() {
+        let set = set("/root", &["*.log", "!keep.log"]);
+        assert!(set.is_ignored(Path::new("/root/debug.log"), false));
+        assert!(!set.is_ignored(Path::new("/root/keep.log"), false));
+    }
+
+    #[test]
+    fn test_double_star_spans_segments() {
+        let set = set("/root", &["**/.git/*"]);
+        assert!(set.is_ignored(Path::new("/root/a/b/.git/config"), false));
+    }
+}
@@ -3,10 +3,14 @@ use std::env;
 use std::path::Path;
 
 mod dedup;
-mod file_iter;
+mod filter;
+mod ignore;
+mod report;
 mod types;
-use dedup::find_duplicates;
+use dedup::{delete_duplicates, find_duplicates, DeleteMethod};
 
+use crate::ignore::IgnoreSet;
+use crate::report::OutputFormat;
 use crate::types::DedupOptions;
 
 fn format_size(size: u64) -> String {
@@ -25,6 +29,37 @@ fn format_size(size: u64) -> String {
     }
 }
 
+// Parses a byte count that may carry a binary unit suffix, e.g. "4096", "512K",
+// "10M" or "2GB". Suffixes are case-insensitive and 1024-based.
+fn parse_size(input: &str) -> Result<u64> {
+    let upper = input.trim().to_ascii_uppercase();
+    const KB: u64 = 1024;
+    const MB: u64 = KB * 1024;
+    const GB: u64 = MB * 1024;
+
+    for (suffix, multiplier) in [
+        ("KB", KB),
+        ("MB", MB),
+        ("GB", GB),
+        ("K", KB),
+        ("M", MB),
+        ("G", GB),
+        ("B", 1),
+    ] {
+        if let Some(number) = upper.strip_suffix(suffix) {
+            let value: u64 = number
+                .trim()
+                .parse()
+                .map_err(|_| anyhow::anyhow!("Invalid size '{}'", input))?;
+            return Ok(value * multiplier);
+        }
+    }
+
+    upper
+        .parse()
+        .map_err(|_| anyhow::anyhow!("Invalid size '{}'", input))
+}
+
 fn main() -> Result<()> {
     let args: Vec<String> = env::args().collect();
 
@@ -41,6 +76,16 @@ fn main() -> Result<()> {
         eprintln!("  --exclude-empty      Exclude files with zero size from duplicate search");
         eprintln!("  --size-only          Compare files only by size, not content");
         eprintln!("  --case-insensitive   Use case-insensitive filter matching");
+        eprintln!(
+            "  --delete=<method>    Delete duplicates; method is one of none (default),"
+        );
+        eprintln!(
+            "                       all-except-newest, all-except-oldest, one-newest, one-oldest"
+        );
+        eprintln!("  --ignore-file <path> Apply gitignore-style patterns from <path>");
+        eprintln!("  --threads <N>        Worker threads to use (0 = auto, the default)");
+        eprintln!("  --format=<fmt>       Output format: text (default), json or csv");
+        eprintln!("  --min-size <bytes>   Ignore files smaller than <bytes> (accepts K/M/G suffixes)");
         std::process::exit(1);
     }
 
@@ -49,15 +94,70 @@ fn main() -> Result<()> {
     let mut exclude_empty = false;
     let mut size_only = false;
     let mut case_sensitive = true;
+    let mut delete_method = DeleteMethod::None;
+    let mut ignore = IgnoreSet::default();
+    let mut threads = 0usize;
+    let mut format = OutputFormat::Text;
+    let mut min_size = 0u64;
 
     // Parse arguments
-    for arg in args[2..].iter() {
-        match arg.as_str() {
+    let rest = &args[2..];
+    let mut i = 0;
+    while i < rest.len() {
+        let arg = rest[i].as_str();
+        match arg {
             "--exclude-empty" => exclude_empty = true,
             "--size-only" => size_only = true,
             "--case-insensitive" => case_sensitive = false,
-            _ => filters.push(arg),
+            "--ignore-file" => {
+                let path = rest
+                    .get(i + 1)
+                    .ok_or_else(|| anyhow::anyhow!("--ignore-file requires a path argument"))?;
+                ignore.add_file(Path::new(path))?;
+                i += 1;
+            }
+            "--threads" => {
+                let value = rest
+                    .get(i + 1)
+                    .ok_or_else(|| anyhow::anyhow!("--threads requires a number argument"))?;
+                threads = value
+                    .parse()
+                    .map_err(|_| anyhow::anyhow!("Invalid --threads value '{}'", value))?;
+                i += 1;
+            }
+            "--min-size" => {
+                let value = rest
+                    .get(i + 1)
+                    .ok_or_else(|| anyhow::anyhow!("--min-size requires a size argument"))?;
+                min_size = parse_size(value)?;
+                i += 1;
+            }
+            _ if arg.starts_with("--delete=") => {
+                let value = &arg["--delete=".len()..];
+                delete_method = match DeleteMethod::parse(value) {
+                    Some(method) => method,
+                    None => anyhow::bail!("Unknown --delete method '{}'", value),
+                };
+            }
+            _ if arg.starts_with("--format=") => {
+                let value = &arg["--format=".len()..];
+                format = match OutputFormat::parse(value) {
+                    Some(format) => format,
+                    None => anyhow::bail!("Unknown --format '{}'", value),
+                };
+            }
+            _ => filters.push(&rest[i]),
         }
+        i += 1;
+    }
+
+    // A thread count of 0 leaves rayon to size the pool from the number of
+    // available CPUs.
+    if threads > 0 {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build_global()
+            .map_err(|e| anyhow::anyhow!("Failed to configure thread pool: {}", e))?;
     }
 
     let options = DedupOptions {
@@ -65,12 +165,19 @@ fn main() -> Result<()> {
         exclude_empty,
         case_sensitive,
         only_compare_file_size: size_only,
+        ignore: &ignore,
+        min_size,
     };
 
-    run(folder_path, &options)
+    run(folder_path, &options, delete_method, format)
 }
 
-fn run(folder_path: &str, options: &DedupOptions) -> Result<()> {
+fn run(
+    folder_path: &str,
+    options: &DedupOptions,
+    delete_method: DeleteMethod,
+    format: OutputFormat,
+) -> Result<()> {
     let path = Path::new(folder_path);
     if !path.exists() || !path.is_dir() {
         anyhow::bail!("'{}' is not a valid directory", folder_path);
@@ -78,9 +185,44 @@ fn run(folder_path: &str, options: &DedupOptions) -> Result<()> {
 
     let duplicates = find_duplicates(path, options)?;
 
+    match format {
+        OutputFormat::Text => print_text(options, &duplicates),
+        OutputFormat::Json => println!("{}", report::to_json(&duplicates)?),
+        OutputFormat::Csv => print!("{}", report::to_csv(&duplicates)),
+    }
+
+    if delete_method != DeleteMethod::None {
+        let report = delete_duplicates(&duplicates, delete_method)?;
+
+        // The machine-readable formats keep stdout clean, so the human-readable
+        // deletion summary is only printed alongside the text report.
+        if format == OutputFormat::Text {
+            println!("\nReclaimed space:");
+            for group in &report.groups {
+                println!(
+                    "  {} ({} file(s) removed)",
+                    format_size(group.reclaimed),
+                    group.removed.len()
+                );
+            }
+            println!("Total lost space reclaimed: {}", format_size(report.total_reclaimed));
+        }
+
+        if !report.failures.is_empty() {
+            eprintln!("\nFailed to delete {} file(s):", report.failures.len());
+            for failure in &report.failures {
+                eprintln!("  {}: {}", failure.path.display(), failure.error);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn print_text(options: &DedupOptions, duplicates: &types::DuplicateFiles) {
     if duplicates.groups.is_empty() {
         println!("No duplicate files found.");
-        return Ok(());
+        return;
     }
 
     println!(
@@ -91,7 +233,7 @@ fn run(folder_path: &str, options: &DedupOptions) -> Result<()> {
             ""
         }
     );
-    for group in duplicates.groups {
+    for group in &duplicates.groups {
         let size = group.files[0].metadata.len();
         println!(
             "\nGroup: {} files of size {}",
@@ -102,6 +244,4 @@ fn run(folder_path: &str, options: &DedupOptions) -> Result<()> {
             println!("  {}", file.path.display());
         }
     }
-
-    Ok(())
 }
@@ -0,0 +1,154 @@
+use anyhow::Result;
+use serde::Serialize;
+
+use crate::types::DuplicateFiles;
+
+// How results are rendered to stdout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Text,
+    Json,
+    Csv,
+}
+
+impl OutputFormat {
+    pub fn parse(value: &str) -> Option<OutputFormat> {
+        match value {
+            "text" => Some(OutputFormat::Text),
+            "json" => Some(OutputFormat::Json),
+            "csv" => Some(OutputFormat::Csv),
+            _ => None,
+        }
+    }
+}
+
+// Aggregate counts that accompany the machine-readable output.
+#[derive(Serialize)]
+struct Statistics {
+    files_scanned: usize,
+    duplicate_groups: usize,
+    duplicate_files: usize,
+    lost_space_bytes: u64,
+}
+
+#[derive(Serialize)]
+struct JsonReport<'a> {
+    statistics: Statistics,
+    groups: &'a [crate::types::DuplicateGroup],
+}
+
+fn statistics(duplicates: &DuplicateFiles) -> Statistics {
+    let duplicate_files = duplicates.groups.iter().map(|g| g.files.len()).sum();
+    // Every copy but one in each group is reclaimable.
+    let lost_space_bytes = duplicates
+        .groups
+        .iter()
+        .map(|g| g.files[0].metadata.len() * (g.files.len() as u64 - 1))
+        .sum();
+
+    Statistics {
+        files_scanned: duplicates.scanned_files,
+        duplicate_groups: duplicates.groups.len(),
+        duplicate_files,
+        lost_space_bytes,
+    }
+}
+
+pub fn to_json(duplicates: &DuplicateFiles) -> Result<String> {
+    let report = JsonReport {
+        statistics: statistics(duplicates),
+        groups: &duplicates.groups,
+    };
+    Ok(serde_json::to_string_pretty(&report)?)
+}
+
+// One row per duplicate file: the group index, the path and the size in bytes.
+pub fn to_csv(duplicates: &DuplicateFiles) -> String {
+    let mut out = String::from("group,path,size\n");
+    for (group_id, group) in duplicates.groups.iter().enumerate() {
+        for file in &group.files {
+            out.push_str(&format!(
+                "{},{},{}\n",
+                group_id,
+                csv_field(&file.path.to_string_lossy()),
+                file.metadata.len()
+            ));
+        }
+    }
+    out
+}
+
+// Quote a CSV field when it contains a character that would otherwise break the
+// row, doubling any embedded quotes.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dedup::find_duplicates;
+    use crate::ignore::IgnoreSet;
+    use crate::types::DedupOptions;
+    use std::fs::File;
+    use std::io::Write;
+    use tempfile::tempdir;
+
+    fn two_duplicates() -> DuplicateFiles {
+        let temp_dir = tempdir().unwrap();
+        let content = b"same bytes here";
+        for name in ["a.bin", "b.bin"] {
+            File::create(temp_dir.path().join(name))
+                .unwrap()
+                .write_all(content)
+                .unwrap();
+        }
+
+        let ignore = IgnoreSet::default();
+        let options = DedupOptions {
+            filters: &[],
+            exclude_empty: false,
+            case_sensitive: true,
+            only_compare_file_size: false,
+            ignore: &ignore,
+            min_size: 0,
+        };
+        find_duplicates(temp_dir.path(), &options).unwrap()
+    }
+
+    #[test]
+    fn test_statistics() {
+        let duplicates = two_duplicates();
+        let stats = statistics(&duplicates);
+
+        assert_eq!(stats.files_scanned, 2);
+        assert_eq!(stats.duplicate_groups, 1);
+        assert_eq!(stats.duplicate_files, 2);
+        // Two identical 15-byte files reclaim one copy.
+        assert_eq!(stats.lost_space_bytes, 15);
+    }
+
+    #[test]
+    fn test_csv_has_one_row_per_file() {
+        let duplicates = two_duplicates();
+        let csv = to_csv(&duplicates);
+
+        let lines: Vec<_> = csv.lines().collect();
+        assert_eq!(lines[0], "group,path,size");
+        assert_eq!(lines.len(), 3); // header + two files
+        assert!(lines[1].starts_with("0,"));
+    }
+
+    #[test]
+    fn test_json_is_valid() {
+        let duplicates = two_duplicates();
+        let json = to_json(&duplicates).unwrap();
+        assert!(serde_json::from_str::<serde_json::Value>(&json).is_ok());
+        assert!(json.contains("\"statistics\""));
+        assert!(json.contains("\"lost_space_bytes\": 15"));
+    }
+}
@@ -1,17 +1,68 @@
 use std::path::PathBuf;
 use std::fs::Metadata;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
+
+use crate::ignore::IgnoreSet;
 pub struct FileInfo {
     pub path: PathBuf,
     pub metadata: Metadata,
+    // Content hashes are filled in lazily while grouping: the partial hash
+    // covers only a short prefix, the full hash the whole file. Both stay
+    // `None` until the corresponding phase needs them so a file is never read
+    // more than it has to be.
+    pub partial_hash: Option<u128>,
+    pub full_hash: Option<u128>,
+}
+
+impl FileInfo {
+    pub fn new(path: PathBuf, metadata: Metadata) -> Self {
+        FileInfo {
+            path,
+            metadata,
+            partial_hash: None,
+            full_hash: None,
+        }
+    }
+}
+
+// `Metadata` is not itself serializable, so we project the few fields that are
+// useful in a report: the path, the size, and the timestamps as whole seconds
+// since the Unix epoch (absent when the platform doesn't expose them).
+impl Serialize for FileInfo {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("FileInfo", 4)?;
+        state.serialize_field("path", &self.path)?;
+        state.serialize_field("size", &self.metadata.len())?;
+        state.serialize_field("modified", &unix_seconds(self.metadata.modified().ok()))?;
+        state.serialize_field("created", &unix_seconds(self.metadata.created().ok()))?;
+        state.end()
+    }
+}
+
+fn unix_seconds(time: Option<SystemTime>) -> Option<u64> {
+    time.and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_secs())
 }
 
+#[derive(Serialize)]
 pub struct DuplicateGroup {
     pub files: Vec<FileInfo>,
 }
 
+#[derive(Serialize)]
 pub struct DuplicateFiles {
     pub groups: Vec<DuplicateGroup>,
-} 
+    // Total number of files that passed the filters, before duplicate
+    // detection discarded the unique ones.
+    pub scanned_files: usize,
+    // Whether the groups were formed by size alone. Size-only groups are not
+    // verified duplicates, so deletion must refuse to act on them.
+    #[serde(skip)]
+    pub size_only: bool,
+}
 
 #[derive(Debug, Clone)]
 pub struct DedupOptions<'a> {
@@ -19,4 +70,7 @@ pub struct DedupOptions<'a> {
     pub exclude_empty: bool,
     pub case_sensitive: bool,
     pub only_compare_file_size: bool,
-}
\ No newline at end of file
+    pub ignore: &'a IgnoreSet,
+    // Files smaller than this are skipped entirely (0 disables the threshold).
+    pub min_size: u64,
+}